@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 use std::env;
 use std::fs::{self, File, OpenOptions};
 use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // --- New: Configuration Struct ---
 // This struct holds our application's configuration.
@@ -13,25 +13,76 @@ use std::path::PathBuf;
 #[derive(Serialize, Deserialize, Debug, Default)]
 struct Config {
     global_file: Option<PathBuf>,
+
+    /// Full URL of the OpenAI-compatible completions endpoint to call.
+    #[serde(default)]
+    api_url: Option<String>,
+    /// Model identifier passed through to the completions endpoint.
+    #[serde(default)]
+    model: Option<String>,
+    /// Maximum number of tokens to request from the model.
+    #[serde(default)]
+    max_tokens: Option<u32>,
+    /// Sampling temperature passed through to the completions endpoint.
+    #[serde(default)]
+    temperature: Option<f32>,
+    /// Optional HTTP(S) proxy to route LLM requests through.
+    #[serde(default)]
+    proxy: Option<String>,
+    /// Fallback API key used when the `OPENROUTER_API_KEY` environment
+    /// variable isn't set.
+    #[serde(default)]
+    api_key: Option<String>,
+    /// When true, `Create` skips the LLM round-trip and formats tasks locally.
+    #[serde(default)]
+    dry_run: bool,
 }
 
+const DEFAULT_API_URL: &str = "https://openrouter.ai/api/v1/completions";
+const DEFAULT_MODEL: &str = "google/gemini-2.0-flash-001";
+const DEFAULT_MAX_TOKENS: u32 = 100;
+const DEFAULT_TEMPERATURE: f32 = 0.7;
+
 #[derive(Parser, Debug)]
 #[command(version = "0.1", about = "CLI to manage Markdown Tasks", long_about = None)]
 struct Cli {
+    /// Overrides the path of the configuration file. Falls back to the
+    /// `TASKS_CONFIG_PATH` environment variable, then to the OS default.
+    #[arg(long, global = true)]
+    config_path: Option<PathBuf>,
+
     #[command(subcommand)]
     action: Action,
 }
 
 #[derive(Subcommand, Debug)]
 enum Action {
+    /// Scaffold a new tasks file and seed the global config.
+    Init(InitArgs),
     /// Create a new task.
     Create(CreateArgs),
-    /// Delete a task (not yet implemented).
-    Delete,
+    /// Delete one or more tasks.
+    Delete(DeleteArgs),
+    /// List all tasks.
+    List(ListArgs),
+    /// Toggle a task between done and not done.
+    Done(DoneArgs),
     /// Manage application configuration.
     Config(ConfigArgs),
 }
 
+#[derive(Parser, Debug)]
+struct InitArgs {
+    /// Directory to scaffold the tasks file in.
+    #[arg(default_value = ".")]
+    entry: PathBuf,
+
+    /// Name of the tasks file to create, without the `.md` extension.
+    /// Defaults to the directory name.
+    #[arg(long)]
+    name: Option<String>,
+}
+
 #[derive(Parser, Debug)]
 struct CreateArgs {
     /// The content of the task to create.
@@ -41,13 +92,211 @@ struct CreateArgs {
     /// Path of the file to use. Overrides the global config.
     #[arg(long)]
     file: Option<PathBuf>,
+
+    /// Skip the LLM round-trip and format the task locally. Overrides the
+    /// `dry_run` config setting when set.
+    #[arg(long, conflicts_with = "no_dry_run")]
+    dry_run: bool,
+
+    /// Force the LLM round-trip even if `dry_run` is enabled in the config.
+    #[arg(long)]
+    no_dry_run: bool,
+}
+
+#[derive(Parser, Debug)]
+struct DeleteArgs {
+    /// The 1-based index of the task to delete, as shown by `tasks list`.
+    #[arg(long, conflicts_with = "all_done")]
+    index: Option<usize>,
+
+    /// Delete every task that is marked as done instead of a single index.
+    #[arg(long, conflicts_with = "index")]
+    all_done: bool,
+
+    /// Path of the file to use. Overrides the global config.
+    #[arg(long)]
+    file: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+struct ListArgs {
+    /// Path of the file to use. Overrides the global config.
+    #[arg(long)]
+    file: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+struct DoneArgs {
+    /// The 1-based index of the task to toggle, as shown by `tasks list`.
+    #[arg(long)]
+    index: usize,
+
+    /// Path of the file to use. Overrides the global config.
+    #[arg(long)]
+    file: Option<PathBuf>,
 }
 
 #[derive(Parser, Debug)]
 struct ConfigArgs {
     /// Sets the global file path for all tasks.
     #[arg(long)]
-    global_file: PathBuf,
+    global_file: Option<PathBuf>,
+
+    /// Sets the model identifier used when improving tasks with the LLM.
+    #[arg(long)]
+    model: Option<String>,
+
+    /// Sets the full URL of the OpenAI-compatible completions endpoint.
+    #[arg(long)]
+    api_url: Option<String>,
+
+    /// Sets the maximum number of tokens requested from the model.
+    #[arg(long)]
+    max_tokens: Option<u32>,
+
+    /// Sets the sampling temperature used when improving tasks with the LLM.
+    #[arg(long)]
+    temperature: Option<f32>,
+}
+
+// --- New: Task parsing ---
+// A single checkbox task line parsed out of the Markdown file, tolerating the
+// emoji decorations we add in `Create` (`📋` prefix, trailing `🕓<timestamp>`)
+// as well as arbitrary surrounding text.
+#[derive(Debug, Clone, PartialEq)]
+struct TaskLine {
+    /// Zero-based index into the file's lines, used to rewrite the line in place.
+    line_index: usize,
+    done: bool,
+    body: String,
+    timestamp: Option<String>,
+}
+
+impl TaskLine {
+    /// Tries to parse a single Markdown line as a `- [ ]` / `- [x]` checkbox task.
+    /// Returns `None` for lines that aren't tasks.
+    fn parse(line_index: usize, line: &str) -> Option<TaskLine> {
+        let trimmed = line.trim_start();
+        let (done, rest) = if let Some(rest) = trimmed.strip_prefix("- [ ]") {
+            (false, rest)
+        } else if let Some(rest) = trimmed
+            .strip_prefix("- [x]")
+            .or_else(|| trimmed.strip_prefix("- [X]"))
+        {
+            (true, rest)
+        } else {
+            return None;
+        };
+
+        let rest = rest.trim_start();
+        let rest = rest.strip_prefix('📋').unwrap_or(rest);
+
+        let (body, timestamp) = match rest.rsplit_once('🕓') {
+            Some((body, ts)) => {
+                // Only strip the " - " separator we ourselves append before the
+                // timestamp emoji; a body that legitimately ends in "-" must
+                // survive parsing untouched.
+                let body = body.strip_suffix(" - ").unwrap_or(body).trim_end();
+                (body, Some(ts.trim().to_string()))
+            }
+            None => (rest.trim_end(), None),
+        };
+
+        Some(TaskLine {
+            line_index,
+            done,
+            body: body.to_string(),
+            timestamp,
+        })
+    }
+}
+
+/// Flips the `[ ]`/`[x]` checkbox of a parsed task line in place, leaving the
+/// rest of the line (decorations, body, surrounding text) exactly as it was.
+/// This avoids re-emitting a canonical form that would inject our own
+/// decorations into a line that never had them.
+fn toggle_checkbox(line: &str) -> String {
+    let trimmed = line.trim_start();
+    let prefix = &line[..line.len() - trimmed.len()];
+    if let Some(rest) = trimmed.strip_prefix("- [ ]") {
+        format!("{}- [x]{}", prefix, rest)
+    } else if let Some(rest) = trimmed
+        .strip_prefix("- [x]")
+        .or_else(|| trimmed.strip_prefix("- [X]"))
+    {
+        format!("{}- [ ]{}", prefix, rest)
+    } else {
+        line.to_string()
+    }
+}
+
+/// Reads the task file and returns its raw lines alongside the tasks parsed from them.
+/// Returns an empty result (rather than an error) if the file doesn't exist yet,
+/// so callers can show the same friendly "no tasks found" message either way.
+fn parse_task_file(path: &Path) -> std::io::Result<(Vec<String>, Vec<TaskLine>)> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok((Vec::new(), Vec::new())),
+        Err(e) => return Err(e),
+    };
+    let lines: Vec<String> = contents.lines().map(String::from).collect();
+    let tasks = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| TaskLine::parse(i, line))
+        .collect();
+    Ok((lines, tasks))
+}
+
+/// Writes `lines` to `path` atomically: the new content is written to a temp
+/// file in the same directory, then renamed over `path`, so a crash mid-write
+/// can't truncate the task list.
+fn write_file_atomically(path: &Path, lines: &[String]) -> std::io::Result<()> {
+    let dir = path.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or_else(|| Path::new("."));
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("tasks.md");
+    let tmp_path = dir.join(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+    let mut tmp_file = File::create(&tmp_path)?;
+    for line in lines {
+        writeln!(tmp_file, "{}", line)?;
+    }
+    tmp_file.sync_all()?;
+
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Resolves the OpenRouter API key: the `OPENROUTER_API_KEY` environment
+/// variable wins, falling back to the `api_key` field in the config file.
+fn resolve_api_key(config: &Config) -> String {
+    env::var("OPENROUTER_API_KEY")
+        .ok()
+        .or_else(|| config.api_key.clone())
+        .unwrap_or_else(|| {
+            eprintln!("Error: No API key configured.");
+            eprintln!("Set the OPENROUTER_API_KEY environment variable, or add an");
+            eprintln!("\"api_key\" field to your config file.");
+            std::process::exit(1);
+        })
+}
+
+/// Resolves the task file to operate on: an explicit `--file` flag wins,
+/// falling back to the configured global file.
+fn resolve_file(file: Option<PathBuf>, config: &Config) -> PathBuf {
+    match file.or_else(|| config.global_file.clone()) {
+        Some(path) => path,
+        None => {
+            eprintln!("Error: No file path provided.");
+            eprintln!(
+                "Please specify a file with --file <PATH>, or set a global default with:"
+            );
+            eprintln!("tasks config --global-file <PATH>");
+            std::process::exit(1);
+        }
+    }
 }
 
 #[derive(Serialize, Debug)]
@@ -55,6 +304,7 @@ struct ApiRequest {
     model: String,
     prompt: String,
     max_tokens: u32,
+    temperature: f32,
 }
 
 #[derive(Deserialize, Debug)]
@@ -67,13 +317,12 @@ struct Choice {
     text: String,
 }
 
-async fn improve_task_with_llm(user_input: &str) -> Result<String, reqwest::Error> {
+async fn improve_task_with_llm(user_input: &str, config: &Config) -> Result<String, reqwest::Error> {
     println!("🤖 Calling LLM to improve the task... please wait.");
 
-    let api_url = "https://openrouter.ai/api/v1/completions";
+    let api_url = config.api_url.as_deref().unwrap_or(DEFAULT_API_URL);
 
-    let api_key = env::var("OPENROUTER_API_KEY")
-        .expect("Error: OPENROUTER_API_KEY environment variable not set.");
+    let api_key = resolve_api_key(config);
 
     // Construct the prompt for the LLM.
     let prompt = format!(
@@ -84,12 +333,18 @@ async fn improve_task_with_llm(user_input: &str) -> Result<String, reqwest::Erro
 
     // Create the JSON payload for the request.
     let request_payload = ApiRequest {
-        model: "google/gemini-2.0-flash-001".to_string(),
+        model: config.model.clone().unwrap_or_else(|| DEFAULT_MODEL.to_string()),
         prompt,
-        max_tokens: 100,
+        max_tokens: config.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+        temperature: config.temperature.unwrap_or(DEFAULT_TEMPERATURE),
     };
 
-    let client = reqwest::Client::new();
+    let mut client_builder = reqwest::Client::builder();
+    if let Some(proxy) = &config.proxy {
+        client_builder = client_builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    let client = client_builder.build()?;
+
     let response = client
         .post(api_url)
         .bearer_auth(api_key)
@@ -116,29 +371,71 @@ async fn improve_task_with_llm(user_input: &str) -> Result<String, reqwest::Erro
 #[tokio::main]
 async fn main() -> std::io::Result<()> {
     let cli = Cli::parse();
-    let mut config = load_config()?;
+    let mut config = load_config(cli.config_path.as_ref())?;
 
     match cli.action {
-        Action::Create(args) => {
-            let path = match args.file.or(config.global_file) {
-                Some(path) => path,
-                None => {
-                    eprintln!("Error: No file path provided.");
+        Action::Init(args) => {
+            let path = if args.entry.is_dir() {
+                let name = args.name.unwrap_or_else(|| {
+                    args.entry
+                        .canonicalize()
+                        .unwrap_or_else(|_| args.entry.clone())
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("tasks")
+                        .to_string()
+                });
+                args.entry.join(format!("{}.md", name))
+            } else {
+                args.entry
+            };
+
+            if path.exists() {
+                eprintln!("Error: already initialised at {:?}", path);
+                std::process::exit(1);
+            }
+
+            fs::write(&path, "# Tasks\n\n")?;
+
+            if config.global_file.is_none() {
+                if env::var("TASKS_CONFIG").is_ok() {
                     eprintln!(
-                        "Please specify a file with --file <PATH>, or set a global default with:"
+                        "Warning: TASKS_CONFIG is set, so the global file can't be persisted."
                     );
-                    eprintln!("tasks config --global-file <PATH>");
-                    std::process::exit(1);
+                    eprintln!(
+                        "Pass --file {:?} to Create explicitly, or add \"global_file\" to TASKS_CONFIG yourself.",
+                        path
+                    );
+                } else {
+                    config.global_file = Some(path.clone());
+                    save_config(&config, cli.config_path.as_ref())?;
                 }
+            }
+
+            println!("✅ Initialised tasks file at {:?}", path);
+        }
+        Action::Create(args) => {
+            let path = resolve_file(args.file, &config);
+            let dry_run = if args.dry_run {
+                true
+            } else if args.no_dry_run {
+                false
+            } else {
+                config.dry_run
             };
 
-            let improved_content = improve_task_with_llm(&args.content)
-                .await
-                .unwrap_or_else(|e| {
-                    eprintln!("Failed to call LLM API: {}. Using original task.", e);
-                    // Fallback if the API call completely fails
-                    format!("- [ ] 📋{}", args.content)
-                });
+            let improved_content = if dry_run {
+                println!("⏭️  Skipping LLM step (dry run), formatting task locally.");
+                format!("- [ ] 📋{}", args.content)
+            } else {
+                improve_task_with_llm(&args.content, &config)
+                    .await
+                    .unwrap_or_else(|e| {
+                        eprintln!("Failed to call LLM API: {}. Using original task.", e);
+                        // Fallback if the API call completely fails
+                        format!("- [ ] 📋{}", args.content)
+                    })
+            };
 
             let now = Local::now();
             let now_formated = now.format("%d/%m/%Y %H:%M");
@@ -152,13 +449,121 @@ async fn main() -> std::io::Result<()> {
             println!("\n✅ Successfully added improved task to {:?}", path);
             println!("   > {}", improved_content);
         }
-        Action::Delete => println!("Delete Task"),
+        Action::Delete(args) => {
+            let path = resolve_file(args.file, &config);
+            let (mut lines, tasks) = parse_task_file(&path)?;
+
+            if tasks.is_empty() {
+                println!("No tasks found in {:?}", path);
+                return Ok(());
+            }
+
+            let to_remove: Vec<usize> = if args.all_done {
+                tasks
+                    .iter()
+                    .filter(|t| t.done)
+                    .map(|t| t.line_index)
+                    .collect()
+            } else {
+                match args.index {
+                    Some(index) if index >= 1 && index <= tasks.len() => {
+                        vec![tasks[index - 1].line_index]
+                    }
+                    Some(index) => {
+                        eprintln!("Error: No task at index {}.", index);
+                        std::process::exit(1);
+                    }
+                    None => {
+                        eprintln!("Error: Provide --index <N> or --all-done.");
+                        std::process::exit(1);
+                    }
+                }
+            };
+
+            if to_remove.is_empty() {
+                println!("No matching tasks to delete in {:?}", path);
+                return Ok(());
+            }
+
+            let removed = to_remove.len();
+            let mut line_index = 0;
+            lines.retain(|_| {
+                let keep = !to_remove.contains(&line_index);
+                line_index += 1;
+                keep
+            });
+
+            write_file_atomically(&path, &lines)?;
+            println!("🗑️  Deleted {} task(s) from {:?}", removed, path);
+        }
+        Action::List(args) => {
+            let path = resolve_file(args.file, &config);
+            let (_, tasks) = parse_task_file(&path)?;
+
+            if tasks.is_empty() {
+                println!("No tasks found in {:?}", path);
+                return Ok(());
+            }
+
+            for (i, task) in tasks.iter().enumerate() {
+                let checkbox = if task.done { "x" } else { " " };
+                match &task.timestamp {
+                    Some(ts) => println!("{}. [{}] {} (🕓{})", i + 1, checkbox, task.body, ts),
+                    None => println!("{}. [{}] {}", i + 1, checkbox, task.body),
+                }
+            }
+        }
+        Action::Done(args) => {
+            let path = resolve_file(args.file, &config);
+            let (mut lines, tasks) = parse_task_file(&path)?;
+
+            let task = match tasks.get(args.index.wrapping_sub(1)) {
+                Some(task) if args.index >= 1 => task,
+                _ => {
+                    eprintln!("Error: No task at index {}.", args.index);
+                    std::process::exit(1);
+                }
+            };
+
+            let new_done = !task.done;
+            lines[task.line_index] = toggle_checkbox(&lines[task.line_index]);
+
+            write_file_atomically(&path, &lines)?;
+            println!(
+                "✅ Task {} marked as {}",
+                args.index,
+                if new_done { "done" } else { "not done" }
+            );
+        }
         Action::Config(args) => {
-            config.global_file = Some(args.global_file);
-            save_config(&config)?;
+            if let Some(global_file) = args.global_file {
+                config.global_file = Some(global_file);
+                println!("Global file path successfully set to: {:?}", config.global_file.as_ref().unwrap());
+            }
+            if let Some(model) = args.model {
+                println!("Model successfully set to: {}", model);
+                config.model = Some(model);
+            }
+            if let Some(api_url) = args.api_url {
+                println!("API URL successfully set to: {}", api_url);
+                config.api_url = Some(api_url);
+            }
+            if let Some(max_tokens) = args.max_tokens {
+                println!("Max tokens successfully set to: {}", max_tokens);
+                config.max_tokens = Some(max_tokens);
+            }
+            if let Some(temperature) = args.temperature {
+                println!("Temperature successfully set to: {}", temperature);
+                config.temperature = Some(temperature);
+            }
 
-            if let Some(path) = &config.global_file {
-                println!("Global file path successfully set to: {:?}", path);
+            if env::var("TASKS_CONFIG").is_ok() {
+                eprintln!(
+                    "Warning: TASKS_CONFIG is set, so these changes can't be persisted."
+                );
+                eprintln!("Update TASKS_CONFIG itself instead.");
+            } else {
+                save_config(&config, cli.config_path.as_ref())?;
             }
         }
     };
@@ -167,8 +572,16 @@ async fn main() -> std::io::Result<()> {
 }
 
 /// Gets the path to the configuration file.
-/// Uses the `directories` create to find the appropriate system location.
-fn get_config_path() -> Option<PathBuf> {
+/// An explicit `override_path` wins, then the `TASKS_CONFIG_PATH` environment
+/// variable, then the `directories`-crate default for this OS.
+fn get_config_path(override_path: Option<&PathBuf>) -> Option<PathBuf> {
+    if let Some(path) = override_path {
+        return Some(path.clone());
+    }
+    if let Ok(path) = env::var("TASKS_CONFIG_PATH") {
+        return Some(PathBuf::from(path));
+    }
+
     if let Some(proj_dirs) = ProjectDirs::from("com", "org", "TasksCLI") {
         let config_dir = proj_dirs.config_dir();
         if !config_dir.exists() {
@@ -180,10 +593,25 @@ fn get_config_path() -> Option<PathBuf> {
     }
 }
 
-/// Loads the configuration from the JSON file.
-/// Returns a default config if the file doesn't exist.
-fn load_config() -> std::io::Result<Config> {
-    let config_path = match get_config_path() {
+/// Loads the configuration.
+/// If the `TASKS_CONFIG` environment variable is set, its value is parsed
+/// directly as the config's JSON content and no file is read at all
+/// (useful for CI and containers). Otherwise falls back to the file at
+/// `override_path` / `TASKS_CONFIG_PATH` / the OS default, returning a
+/// default config if that file doesn't exist.
+fn load_config(override_path: Option<&PathBuf>) -> std::io::Result<Config> {
+    if let Ok(contents) = env::var("TASKS_CONFIG") {
+        let config = serde_json::from_str(&contents).unwrap_or_else(|err| {
+            eprintln!(
+                "Warning: Could not parse TASKS_CONFIG, using default. Error: {}",
+                err
+            );
+            Config::default()
+        });
+        return Ok(config);
+    }
+
+    let config_path = match get_config_path(override_path) {
         Some(path) => path,
         None => {
             eprintln!("Error: Could not determine a valid configuration path for the application.");
@@ -210,8 +638,8 @@ fn load_config() -> std::io::Result<Config> {
 }
 
 /// Saves the configuration to the JSON file.
-fn save_config(config: &Config) -> std::io::Result<()> {
-    let config_path = match get_config_path() {
+fn save_config(config: &Config, override_path: Option<&PathBuf>) -> std::io::Result<()> {
+    let config_path = match get_config_path(override_path) {
         Some(path) => path,
         None => {
             eprintln!("Error: Could not determine a valid configuration path to save to.");